@@ -1,15 +1,602 @@
 use csv::WriterBuilder;
-use rstar::{RTree, RTreeObject, PointDistance};
+use geojson::{Feature, Geometry, Value as GeoJsonValue};
+use lazy_static::lazy_static;
+use rand::Rng;
+use rayon::prelude::*;
+use regex::Regex;
+use rstar::{RTree, RTreeObject, PointDistance, AABB};
 use rstar::primitives::{PointWithData, RectangleWithData};
 use flatgeobuf::{Geom, GeomType};
 use packed_simd::f64x4;
+use serde::Serialize;
 use serde_wasm_bindgen::SerdeWasmBindgen;
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 use wee_alloc::WeeAlloc;
 
 #[global_allocator]
 static ALLOC: WeeAlloc = WeeAlloc::INIT;
 
+/// Mean earth radius in meters, used by the haversine distance kernel.
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// Great-circle distance between two lat/lon points, in meters.
+fn haversine_distance_meters(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lon1, lat2, lon2) = (
+        lat1.to_radians(),
+        lon1.to_radians(),
+        lat2.to_radians(),
+        lon2.to_radians(),
+    );
+    let dlat = lat2 - lat1;
+    let dlon = lon2 - lon1;
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+}
+
+lazy_static! {
+    static ref YEAR_MONTH_DAY_RE: Regex = Regex::new(r"^(\d{4})-(\d{2})-(\d{2})$").unwrap();
+    static ref YEAR_MONTH_RE: Regex = Regex::new(r"^(\d{4})-(\d{2})$").unwrap();
+    static ref MONTH_YEAR_RE: Regex = Regex::new(r"^(\d{2})/(\d{4})$").unwrap();
+    static ref YEAR_RANGE_RE: Regex = Regex::new(r"^(\d{4})\s*(?:-|\.\.\.?)\s*(\d{4})$").unwrap();
+    static ref DECADE_RE: Regex = Regex::new(r"^(\d{4})s$").unwrap();
+    static ref APPROX_RE: Regex = Regex::new(r"^(?:before|~)\s*(\d{4})$").unwrap();
+    static ref CENTURY_RE: Regex = Regex::new(r"(?i)^(early|mid|late)?\s*c(\d{1,2})$").unwrap();
+    static ref YEAR_RE: Regex = Regex::new(r"^(\d{4})$").unwrap();
+}
+
+/// Parses the fuzzy/imprecise date strings found in real activist/OSM-style
+/// datasets (`1990s`, `~1885`, `before 1800`, `C19`, `2011-03`, `1990-1999`,
+/// `05/2014`, ...) into a comparable year-based ordinal. Century notation
+/// (`C19`, optionally qualified `early`/`mid`/`late`) resolves to a
+/// representative year within the century; ranges take their start year.
+/// Returns `None` for anything that doesn't match a known pattern, so the
+/// caller can fall back explicitly instead of silently collapsing to zero.
+fn parse_fuzzy_date(raw: &str) -> Option<f64> {
+    let s = raw.trim();
+
+    if let Some(caps) = YEAR_MONTH_DAY_RE.captures(s) {
+        let year: f64 = caps[1].parse().ok()?;
+        let month: f64 = caps[2].parse().ok()?;
+        let day: f64 = caps[3].parse().ok()?;
+        return Some(year + (month - 1.0) / 12.0 + (day - 1.0) / 365.0);
+    }
+
+    if let Some(caps) = YEAR_MONTH_RE.captures(s) {
+        let year: f64 = caps[1].parse().ok()?;
+        let month: f64 = caps[2].parse().ok()?;
+        return Some(year + (month - 1.0) / 12.0);
+    }
+
+    if let Some(caps) = MONTH_YEAR_RE.captures(s) {
+        let month: f64 = caps[1].parse().ok()?;
+        let year: f64 = caps[2].parse().ok()?;
+        return Some(year + (month - 1.0) / 12.0);
+    }
+
+    if let Some(caps) = YEAR_RANGE_RE.captures(s) {
+        // Range queries should be able to match on the start of the range.
+        return caps[1].parse().ok();
+    }
+
+    if let Some(caps) = DECADE_RE.captures(s) {
+        return caps[1].parse().ok();
+    }
+
+    if let Some(caps) = APPROX_RE.captures(s) {
+        return caps[1].parse().ok();
+    }
+
+    if let Some(caps) = CENTURY_RE.captures(s) {
+        let century: f64 = caps[2].parse().ok()?;
+        let century_start = (century - 1.0) * 100.0 + 1.0;
+        let offset = match caps.get(1).map(|m| m.as_str().to_lowercase()).as_deref() {
+            Some("early") => 20.0,
+            Some("late") => 80.0,
+            _ => 50.0,
+        };
+        return Some(century_start + offset);
+    }
+
+    if let Some(caps) = YEAR_RE.captures(s) {
+        return caps[1].parse().ok();
+    }
+
+    None
+}
+
+/// Parses a `_vectors`/`embedding` property value (a JSON array of floats,
+/// e.g. `"[0.12, -0.4, 0.91]"`) into an embedding vector.
+fn parse_embedding(raw: &str) -> Vec<f32> {
+    serde_json::from_str(raw).unwrap_or_default()
+}
+
+/// Squared Euclidean distance between two equal-length vectors, computed
+/// four lanes at a time with `packed_simd`.
+fn l2_distance_squared(a: &[f32], b: &[f32]) -> f64 {
+    let mut sum = f64x4::splat(0.0);
+    let mut chunks_a = a.chunks_exact(4);
+    let mut chunks_b = b.chunks_exact(4);
+
+    for (ca, cb) in (&mut chunks_a).zip(&mut chunks_b) {
+        let va = f64x4::new(ca[0] as f64, ca[1] as f64, ca[2] as f64, ca[3] as f64);
+        let vb = f64x4::new(cb[0] as f64, cb[1] as f64, cb[2] as f64, cb[3] as f64);
+        let diff = va - vb;
+        sum += diff * diff;
+    }
+
+    let mut total: f64 = sum.sum();
+    for (x, y) in chunks_a.remainder().iter().zip(chunks_b.remainder()) {
+        let diff = *x as f64 - *y as f64;
+        total += diff * diff;
+    }
+    total
+}
+
+/// Cosine distance (`1 - cosine similarity`) between two equal-length
+/// vectors, computed four lanes at a time with `packed_simd`.
+fn cosine_distance(a: &[f32], b: &[f32]) -> f64 {
+    let mut dot = f64x4::splat(0.0);
+    let mut norm_a = f64x4::splat(0.0);
+    let mut norm_b = f64x4::splat(0.0);
+    let mut chunks_a = a.chunks_exact(4);
+    let mut chunks_b = b.chunks_exact(4);
+
+    for (ca, cb) in (&mut chunks_a).zip(&mut chunks_b) {
+        let va = f64x4::new(ca[0] as f64, ca[1] as f64, ca[2] as f64, ca[3] as f64);
+        let vb = f64x4::new(cb[0] as f64, cb[1] as f64, cb[2] as f64, cb[3] as f64);
+        dot += va * vb;
+        norm_a += va * va;
+        norm_b += vb * vb;
+    }
+
+    let mut dot_total = dot.sum();
+    let mut norm_a_total = norm_a.sum();
+    let mut norm_b_total = norm_b.sum();
+
+    for (x, y) in chunks_a.remainder().iter().zip(chunks_b.remainder()) {
+        let (x, y) = (*x as f64, *y as f64);
+        dot_total += x * y;
+        norm_a_total += x * x;
+        norm_b_total += y * y;
+    }
+
+    let denom = (norm_a_total.sqrt() * norm_b_total.sqrt()).max(f64::EPSILON);
+    1.0 - (dot_total / denom)
+}
+
+/// A node/distance pair ordered by distance, for use in the binary heaps
+/// that drive HNSW's beam search. Embeddings are never NaN in practice, so
+/// ties in `partial_cmp` fall back to `Equal` rather than panicking.
+#[derive(Clone, Copy)]
+struct OrderedNode(f64, u32);
+
+impl PartialEq for OrderedNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl Eq for OrderedNode {}
+impl PartialOrd for OrderedNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OrderedNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+/// Which kernel `HnswIndex` uses to compare embeddings.
+#[derive(Clone, Copy)]
+enum Metric {
+    Cosine,
+    L2,
+}
+
+/// Approximate nearest-neighbor index over event embeddings, built as a
+/// Hierarchical Navigable Small World graph (Malkov & Yashunin, 2016). Each
+/// node is assigned a random top layer on insertion, with adjacency stored
+/// per layer in `layers[layer][node]`; search descends greedily from the
+/// entry point down to layer 1, then runs a beam search at layer 0.
+#[derive(Clone)]
+struct HnswIndex {
+    vectors: Vec<Vec<f32>>,
+    layers: Vec<Vec<Vec<u32>>>,
+    node_level: Vec<usize>,
+    entry_point: Option<u32>,
+    m: usize,
+    m_max0: usize,
+    ef_construction: usize,
+    level_norm: f64,
+    metric: Metric,
+}
+
+impl HnswIndex {
+    fn new(m: usize, ef_construction: usize) -> Self {
+        HnswIndex {
+            vectors: Vec::new(),
+            layers: Vec::new(),
+            node_level: Vec::new(),
+            entry_point: None,
+            m,
+            m_max0: m * 2,
+            ef_construction,
+            level_norm: 1.0 / (m as f64).ln(),
+            metric: Metric::Cosine,
+        }
+    }
+
+    fn distance(&self, a: &[f32], b: &[f32]) -> f64 {
+        match self.metric {
+            Metric::Cosine => cosine_distance(a, b),
+            Metric::L2 => l2_distance_squared(a, b),
+        }
+    }
+
+    /// Draws a random insertion level with probability decaying
+    /// geometrically, per the HNSW paper's `level = floor(-ln(u) * mL)`.
+    fn random_level(&self, rng: &mut impl FnMut() -> f64) -> usize {
+        let uniform = rng().max(f64::MIN_POSITIVE);
+        (-uniform.ln() * self.level_norm).floor() as usize
+    }
+
+    /// Greedy best-first descent toward `query` at a single layer, used to
+    /// find a good entry point before the beam search below it begins.
+    fn search_layer_greedy(&self, query: &[f32], entry: u32, layer: usize) -> u32 {
+        let mut current = entry;
+        let mut current_dist = self.distance(query, &self.vectors[current as usize]);
+
+        loop {
+            let mut improved = false;
+            if let Some(neighbors) = self.layers[layer].get(current as usize) {
+                for &neighbor in neighbors {
+                    let dist = self.distance(query, &self.vectors[neighbor as usize]);
+                    if dist < current_dist {
+                        current = neighbor;
+                        current_dist = dist;
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        current
+    }
+
+    /// Beam search of width `ef` at a single layer, starting from `entry`.
+    /// `candidates` is a min-heap of nodes still to explore; `found` is a
+    /// max-heap bounded to size `ef` that tracks the current result set, so
+    /// the early-exit check compares against the current ef-th best
+    /// distance rather than the farthest node ever seen across the whole
+    /// traversal. Returns candidates ordered nearest-first.
+    fn search_layer(&self, query: &[f32], entry: u32, layer: usize, ef: usize) -> Vec<(f64, u32)> {
+        let ef = ef.max(1);
+        let mut visited = std::collections::HashSet::new();
+        visited.insert(entry);
+
+        let entry_dist = self.distance(query, &self.vectors[entry as usize]);
+        let mut candidates = std::collections::BinaryHeap::new();
+        candidates.push(std::cmp::Reverse(OrderedNode(entry_dist, entry)));
+
+        let mut found = std::collections::BinaryHeap::new();
+        found.push(OrderedNode(entry_dist, entry));
+
+        while let Some(std::cmp::Reverse(OrderedNode(dist, node))) = candidates.pop() {
+            let furthest = found.peek().map(|n| n.0).unwrap_or(f64::INFINITY);
+            if dist > furthest && found.len() >= ef {
+                break;
+            }
+
+            if let Some(neighbors) = self.layers[layer].get(node as usize) {
+                for &neighbor in neighbors {
+                    if !visited.insert(neighbor) {
+                        continue;
+                    }
+                    let neighbor_dist = self.distance(query, &self.vectors[neighbor as usize]);
+                    let worst = found.peek().map(|n| n.0).unwrap_or(f64::INFINITY);
+
+                    if found.len() < ef || neighbor_dist < worst {
+                        candidates.push(std::cmp::Reverse(OrderedNode(neighbor_dist, neighbor)));
+                        found.push(OrderedNode(neighbor_dist, neighbor));
+                        if found.len() > ef {
+                            found.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<(f64, u32)> = found.into_iter().map(|n| (n.0, n.1)).collect();
+        results.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        results
+    }
+
+    /// Selects up to `m_max` neighbors from `candidates` (which must already
+    /// be sorted nearest-query-first), preferring diverse neighbors over raw
+    /// proximity: a candidate is skipped if it's closer to an
+    /// already-selected neighbor than it is to the query, since that
+    /// neighbor already covers the same region of the embedding space. This
+    /// is the heuristic selection from the HNSW paper, and is what keeps a
+    /// node from getting stranded behind a cluster of near-duplicates.
+    fn select_neighbors_heuristic(&self, candidates: &[(f64, u32)], m_max: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::new();
+
+        for &(candidate_dist, candidate) in candidates {
+            if selected.len() >= m_max {
+                break;
+            }
+
+            let candidate_vector = &self.vectors[candidate as usize];
+            let dominated = selected.iter().any(|&existing| {
+                self.distance(candidate_vector, &self.vectors[existing as usize]) < candidate_dist
+            });
+
+            if !dominated {
+                selected.push(candidate);
+            }
+        }
+
+        selected
+    }
+
+    /// Inserts `vector` as a new node, wiring it into every layer up to its
+    /// randomly-drawn level. Returns `None` for a zero-length vector rather
+    /// than corrupting the graph with an unsearchable node; the first
+    /// non-empty vector inserted becomes the global entry point.
+    fn insert(&mut self, vector: Vec<f32>, rng: &mut impl FnMut() -> f64) -> Option<u32> {
+        if vector.is_empty() {
+            return None;
+        }
+
+        let node = self.vectors.len() as u32;
+        self.vectors.push(vector);
+
+        let level = self.random_level(rng);
+        self.node_level.push(level);
+        while self.layers.len() <= level {
+            self.layers.push(Vec::new());
+        }
+        for layer in self.layers.iter_mut() {
+            while layer.len() <= node as usize {
+                layer.push(Vec::new());
+            }
+        }
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(node);
+            return Some(node);
+        };
+
+        let query = self.vectors[node as usize].clone();
+        let top_level = self.node_level[entry_point as usize];
+        let mut entry = entry_point;
+
+        for layer in (level + 1..=top_level).rev() {
+            entry = self.search_layer_greedy(&query, entry, layer);
+        }
+
+        for layer in (0..=level.min(top_level)).rev() {
+            let m_max = if layer == 0 { self.m_max0 } else { self.m };
+            let candidates = self.search_layer(&query, entry, layer, self.ef_construction);
+            let selected = self.select_neighbors_heuristic(&candidates, m_max);
+
+            self.layers[layer][node as usize] = selected.clone();
+            for &neighbor in &selected {
+                let neighbor_vector = self.vectors[neighbor as usize].clone();
+                let mut neighbor_links = self.layers[layer][neighbor as usize].clone();
+                if !neighbor_links.contains(&node) {
+                    neighbor_links.push(node);
+                }
+                if neighbor_links.len() > m_max {
+                    // Prefer diverse neighbors over raw proximity so a node
+                    // doesn't get stranded behind a cluster of near-duplicates.
+                    let mut ranked: Vec<(f64, u32)> = neighbor_links
+                        .iter()
+                        .map(|&n| (self.distance(&neighbor_vector, &self.vectors[n as usize]), n))
+                        .collect();
+                    ranked.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+                    neighbor_links = self.select_neighbors_heuristic(&ranked, m_max);
+                }
+                self.layers[layer][neighbor as usize] = neighbor_links;
+            }
+
+            if let Some(&(_, closest)) = candidates.first() {
+                entry = closest;
+            }
+        }
+
+        if level > top_level {
+            self.entry_point = Some(node);
+        }
+
+        Some(node)
+    }
+
+    /// Returns up to `k` nodes nearest to `query`, searched with beam width
+    /// `ef`. Returns nothing for an empty graph or an empty query vector.
+    fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(u32, f64)> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let top_level = self.node_level[entry_point as usize];
+        let mut entry = entry_point;
+        for layer in (1..=top_level).rev() {
+            entry = self.search_layer_greedy(query, entry, layer);
+        }
+
+        let mut candidates = self.search_layer(query, entry, 0, ef.max(k));
+        candidates.truncate(k);
+        candidates.into_iter().map(|(dist, node)| (node, dist)).collect()
+    }
+}
+
+/// Extracts the `[x, y]` coordinate pair from a GeoJSON `Point` geometry;
+/// any other geometry type is not an activist event and is skipped.
+fn geometry_to_point(geometry: &Geometry) -> Option<Vec<f32>> {
+    match &geometry.value {
+        GeoJsonValue::Point(coords) => Some(coords.iter().map(|&c| c as f32).collect()),
+        _ => None,
+    }
+}
+
+/// A single field's facet distribution: a value→count map for categorical
+/// fields, or min/max plus an evenly-bucketed histogram for numeric fields.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum FacetValue {
+    Categorical(HashMap<String, u32>),
+    Numeric { min: f64, max: f64, histogram: Vec<u32> },
+}
+
+/// Computes min/max and an evenly-bucketed histogram over a numeric field.
+fn numeric_facet(values: impl Iterator<Item = f64> + Clone, buckets: u32) -> FacetValue {
+    let buckets = buckets.max(1) as usize;
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut count = 0usize;
+
+    for v in values.clone() {
+        min = min.min(v);
+        max = max.max(v);
+        count += 1;
+    }
+
+    if count == 0 {
+        return FacetValue::Numeric {
+            min: 0.0,
+            max: 0.0,
+            histogram: vec![0; buckets],
+        };
+    }
+
+    let mut histogram = vec![0u32; buckets];
+    let span = (max - min).max(f64::EPSILON);
+
+    for v in values {
+        let bucket = (((v - min) / span) * buckets as f64) as usize;
+        histogram[bucket.min(buckets - 1)] += 1;
+    }
+
+    FacetValue::Numeric { min, max, histogram }
+}
+
+/// Sizes `load_data`'s `par_chunks` split off the feature count rather than
+/// the raw byte length, since that's the unit `par_chunks` actually slices.
+/// Always returns at least 1, so zero features or zero measured parallelism
+/// never divides by zero or produces an empty chunk.
+fn parallel_chunk_size(feature_count: usize, parallelism: usize) -> usize {
+    (feature_count / parallelism.max(1)).max(1)
+}
+
+/// Shared org/date/sentiment predicate used by `apply_filters` and the geo
+/// query methods so candidates pulled from the tree are narrowed the same way.
+fn matches_filters(
+    event: &ActivistEvent,
+    org: &str,
+    date_range: [f64; 2],
+    sentiment_range: [f64; 2],
+) -> bool {
+    event.org == org
+        && event.date >= date_range[0]
+        && event.date <= date_range[1]
+        && event.sentiment as f64 >= sentiment_range[0]
+        && event.sentiment as f64 <= sentiment_range[1]
+}
+
+/// A single parsed sort term, e.g. the `date` in `"date:desc"` or the
+/// `lat`/`lon` in `"_geoPoint(48.8566,2.3522):asc"`.
+enum SortKey {
+    Date,
+    Sentiment,
+    Momentum,
+    GeoDistance { lat: f64, lon: f64 },
+}
+
+enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Parses a single MeiliSearch-style sort term (e.g. `"date:desc"` or
+/// `"_geoPoint(48.8566,2.3522):asc"`) into a key/direction pair. Unknown or
+/// malformed terms are skipped rather than erroring, so a bad term just
+/// drops out of the sort instead of failing the whole query.
+fn parse_sort_term(term: &str) -> Option<(SortKey, SortDirection)> {
+    let (field, direction) = term.rsplit_once(':')?;
+    let direction = match direction {
+        "asc" => SortDirection::Asc,
+        "desc" => SortDirection::Desc,
+        _ => return None,
+    };
+
+    let key = if field == "date" {
+        SortKey::Date
+    } else if field == "sentiment" {
+        SortKey::Sentiment
+    } else if field == "momentum" {
+        SortKey::Momentum
+    } else if let Some(args) = field
+        .strip_prefix("_geoPoint(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let mut parts = args.split(',');
+        let lat: f64 = parts.next()?.trim().parse().ok()?;
+        let lon: f64 = parts.next()?.trim().parse().ok()?;
+        SortKey::GeoDistance { lat, lon }
+    } else {
+        return None;
+    };
+
+    Some((key, direction))
+}
+
+/// Orders two events on a single sort key, honoring direction. Float
+/// comparisons that can't be ordered (NaN) are treated as equal rather than
+/// panicking.
+fn compare_by(
+    a: &ActivistEvent,
+    b: &ActivistEvent,
+    key: &SortKey,
+    direction: &SortDirection,
+) -> std::cmp::Ordering {
+    let ordering = match key {
+        SortKey::Date => a
+            .date
+            .partial_cmp(&b.date)
+            .unwrap_or(std::cmp::Ordering::Equal),
+        SortKey::Sentiment => a
+            .sentiment
+            .partial_cmp(&b.sentiment)
+            .unwrap_or(std::cmp::Ordering::Equal),
+        SortKey::Momentum => a
+            .momentum
+            .partial_cmp(&b.momentum)
+            .unwrap_or(std::cmp::Ordering::Equal),
+        SortKey::GeoDistance { lat, lon } => {
+            let da = haversine_distance_meters(*lat, *lon, a.geometry[1] as f64, a.geometry[0] as f64);
+            let db = haversine_distance_meters(*lat, *lon, b.geometry[1] as f64, b.geometry[0] as f64);
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        }
+    };
+
+    match direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    }
+}
+
+#[derive(Clone, Serialize)]
 #[wasm_bindgen]
 struct ActivistEvent {
     id: String,
@@ -18,12 +605,18 @@ struct ActivistEvent {
     sentiment: f32,
     momentum: f32,
     geometry: Vec<f32>,
+    embedding: Vec<f32>,
 }
 
 #[wasm_bindgen]
 pub struct GeoProcessor {
     events: Vec<ActivistEvent>,
     spatial_index: RTree<RectangleWithData<ActivistEvent>>,
+    embedding_index: HnswIndex,
+    // `HnswIndex` skips events with an empty embedding, so its node ids don't
+    // line up positionally with `events` once any such event exists. This
+    // maps an HNSW node id back to the `events` index it came from.
+    embedding_node_to_event: Vec<u32>,
 }
 
 #[wasm_bindgen]
@@ -33,55 +626,105 @@ impl GeoProcessor {
         GeoProcessor {
             events: Vec::new(),
             spatial_index: RTree::new(),
+            embedding_index: HnswIndex::new(16, 200),
+            embedding_node_to_event: Vec::new(),
         }
     }
 
+    /// Parses every feature, then builds the spatial and embedding indexes.
+    /// Features are collected once off the (inherently sequential) FlatGeobuf
+    /// reader, then parsed in parallel across chunks sized from the feature
+    /// count divided by the available parallelism — mirroring how
+    /// MeiliSearch sizes its indexing chunks by input size and thread count,
+    /// adapted here to the unit `par_chunks` actually slices (records, not
+    /// bytes). The RTree is bulk-loaded from the combined point set in one
+    /// shot rather than built via repeated `insert`, which is both faster
+    /// and produces a better-balanced tree.
     #[wasm_bindgen]
-    pub fn load_data(&mut self, data: &[u8]) -> Result<GeoProcessor, JsValue> {
+    pub fn load_data(&mut self, data: &[u8]) -> Result<(), JsValue> {
         let reader = std::io::Cursor::new(data);
-        let mut events = Vec::new();
-        let mut spatial_index = RTree::new();
-
-        for feature in flatgeobuf::read(reader).map_err(|e| JsValue::from(e))? {
-            if let Some(Geom::Point(point)) = feature.geometry.as_point() {
-                let event = ActivistEvent {
-                    id: feature.id.clone(),
-                    org: feature.properties.get("org").unwrap_or("").to_string(),
-                    date: feature
-                        .properties
-                        .get("date")
-                        .unwrap_or(&0.0)
-                        .parse()
-                        .unwrap_or(0.0),
-                    sentiment: feature
-                        .properties
-                        .get("sentiment")
-                        .unwrap_or(&0.0)
-                        .parse()
-                        .unwrap_or(0.0) as f32,
-                    momentum: feature
-                        .properties
-                        .get("momentum")
-                        .unwrap_or(&0.0)
-                        .parse()
-                        .unwrap_or(0.0) as f32,
-                    geometry: point.clone(),
-                };
-                events.push(event);
-                spatial_index.insert(RectangleWithData {
-                    mbr: [point[0], point[1], point[0], point[1]],
-                    data: event.clone(),
-                });
-            }
-        }
-
-        self.events = events;
-        self.spatial_index = spatial_index;
-
-        Ok(GeoProcessor {
-            events: self.events.clone(),
-            spatial_index: self.spatial_index.clone(),
-        })
+        let features: Vec<_> = flatgeobuf::read(reader)
+            .map_err(|e| JsValue::from(e))?
+            .collect();
+
+        let parallelism = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let chunk_size = parallel_chunk_size(features.len(), parallelism);
+
+        let parsed: Vec<ActivistEvent> = features
+            .par_chunks(chunk_size)
+            .flat_map(|chunk| {
+                chunk
+                    .iter()
+                    .filter_map(|feature| {
+                        let Geom::Point(point) = feature.geometry.as_point()? else {
+                            return None;
+                        };
+                        Some(ActivistEvent {
+                            id: feature.id.clone(),
+                            org: feature.properties.get("org").unwrap_or("").to_string(),
+                            date: feature
+                                .properties
+                                .get("date")
+                                .and_then(|raw| parse_fuzzy_date(raw))
+                                .unwrap_or(0.0),
+                            sentiment: feature
+                                .properties
+                                .get("sentiment")
+                                .unwrap_or(&0.0)
+                                .parse()
+                                .unwrap_or(0.0) as f32,
+                            momentum: feature
+                                .properties
+                                .get("momentum")
+                                .unwrap_or(&0.0)
+                                .parse()
+                                .unwrap_or(0.0) as f32,
+                            geometry: point.clone(),
+                            embedding: feature
+                                .properties
+                                .get("_vectors")
+                                .or_else(|| feature.properties.get("embedding"))
+                                .map(|raw| parse_embedding(raw))
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let rectangles: Vec<RectangleWithData<ActivistEvent>> = parsed
+            .iter()
+            .map(|event| RectangleWithData {
+                mbr: [
+                    event.geometry[0],
+                    event.geometry[1],
+                    event.geometry[0],
+                    event.geometry[1],
+                ],
+                data: event.clone(),
+            })
+            .collect();
+        self.spatial_index = RTree::bulk_load(rectangles);
+
+        let mut embedding_index = HnswIndex::new(16, 200);
+        let mut embedding_node_to_event = Vec::new();
+        let mut rng = rand::thread_rng();
+        for (index, event) in parsed.iter().enumerate() {
+            if embedding_index
+                .insert(event.embedding.clone(), &mut || rng.gen::<f64>())
+                .is_some()
+            {
+                embedding_node_to_event.push(index as u32);
+            }
+        }
+        self.embedding_index = embedding_index;
+        self.embedding_node_to_event = embedding_node_to_event;
+
+        self.events = parsed;
+
+        Ok(())
     }
 
     #[wasm_bindgen]
@@ -94,12 +737,7 @@ impl GeoProcessor {
         let mut filtered_events = Vec::new();
 
         for event in &self.events {
-            if event.org == org
-                && event.date >= date_range[0]
-                && event.date <= date_range[1]
-                && event.sentiment as f64 >= sentiment_range[0]
-                && event.sentiment as f64 <= sentiment_range[1]
-            {
+            if matches_filters(event, org, date_range, sentiment_range) {
                 filtered_events.push(event);
             }
         }
@@ -108,6 +746,367 @@ impl GeoProcessor {
         JsValue::from(geojson)
     }
 
+    /// Returns all events within `meters` of (`lat`, `lon`), the wasm
+    /// analogue of MeiliSearch's `_geoRadius` filter. The tree lookup uses a
+    /// planar bounding box around the search circle to gather candidates
+    /// cheaply — on the east-west axis it spans the circle's widest
+    /// longitude reach, which grows toward the poles since a degree of
+    /// longitude shrinks there, so the box can't clip real matches —
+    /// then each candidate is checked against the true haversine distance
+    /// before the existing org/date/sentiment predicates are applied. When
+    /// the widened box would cross the antimeridian (`lon` ± 180), it's
+    /// split into two boxes — one on each side — since the index stores
+    /// longitude unwrapped and a single min/max envelope can't span the
+    /// ±180 seam.
+    #[wasm_bindgen]
+    pub fn query_radius(
+        &self,
+        lat: f64,
+        lon: f64,
+        meters: f64,
+        org: &str,
+        date_range: [f64; 2],
+        sentiment_range: [f64; 2],
+    ) -> JsValue {
+        // Angular radius on the same sphere the haversine check uses, padded
+        // slightly so the box only ever over-fetches candidates, never
+        // under-fetches, since we haversine-check below.
+        let angular_radius = meters / EARTH_RADIUS_METERS * (1.0 + 1e-9);
+        let lat_delta = angular_radius.to_degrees();
+        // Widest longitude reach of the circle; once it touches a pole every
+        // longitude is in range.
+        let lon_delta = if lat.abs().to_radians() + angular_radius < std::f64::consts::FRAC_PI_2 {
+            (angular_radius.sin() / lat.to_radians().cos()).asin().to_degrees()
+        } else {
+            180.0
+        };
+
+        let min_y = lat - lat_delta;
+        let max_y = lat + lat_delta;
+        let raw_min_x = lon - lon_delta;
+        let raw_max_x = lon + lon_delta;
+
+        let mut envelopes = Vec::with_capacity(2);
+        if raw_min_x < -180.0 {
+            envelopes.push(AABB::from_corners([raw_min_x + 360.0, min_y], [180.0, max_y]));
+            envelopes.push(AABB::from_corners([-180.0, min_y], [raw_max_x, max_y]));
+        } else if raw_max_x > 180.0 {
+            envelopes.push(AABB::from_corners([raw_min_x, min_y], [180.0, max_y]));
+            envelopes.push(AABB::from_corners([-180.0, min_y], [raw_max_x - 360.0, max_y]));
+        } else {
+            envelopes.push(AABB::from_corners([raw_min_x, min_y], [raw_max_x, max_y]));
+        }
+
+        let filtered_events: Vec<&ActivistEvent> = envelopes
+            .iter()
+            .flat_map(|envelope| self.spatial_index.locate_in_envelope_intersecting(envelope))
+            .map(|rect| &rect.data)
+            .filter(|event| {
+                haversine_distance_meters(
+                    lat,
+                    lon,
+                    event.geometry[1] as f64,
+                    event.geometry[0] as f64,
+                ) <= meters
+            })
+            .filter(|event| matches_filters(event, org, date_range, sentiment_range))
+            .collect();
+
+        let geojson = serde_json::to_string(&filtered_events).unwrap();
+        JsValue::from(geojson)
+    }
+
+    /// Returns all events inside the axis-aligned `[min_x, min_y, max_x,
+    /// max_y]` box, the wasm analogue of MeiliSearch's `_geoBoundingBox`
+    /// filter.
+    #[wasm_bindgen]
+    pub fn query_bbox(
+        &self,
+        bbox: [f64; 4],
+        org: &str,
+        date_range: [f64; 2],
+        sentiment_range: [f64; 2],
+    ) -> JsValue {
+        let envelope = AABB::from_corners([bbox[0], bbox[1]], [bbox[2], bbox[3]]);
+
+        let filtered_events: Vec<&ActivistEvent> = self
+            .spatial_index
+            .locate_in_envelope_intersecting(&envelope)
+            .map(|rect| &rect.data)
+            .filter(|event| matches_filters(event, org, date_range, sentiment_range))
+            .collect();
+
+        let geojson = serde_json::to_string(&filtered_events).unwrap();
+        JsValue::from(geojson)
+    }
+
+    /// Returns the `k` events nearest to (`lat`, `lon`) by haversine
+    /// distance, the wasm analogue of MeiliSearch's `_geoPoint` sort. The
+    /// tree's own `nearest_neighbor_iter` order is only a planar
+    /// approximation (it distorts east-west distance away from the equator,
+    /// same as `query_radius`'s prefilter), so a generous candidate pool is
+    /// pulled from it and re-ranked by true haversine distance before
+    /// truncating to `k`.
+    #[wasm_bindgen]
+    pub fn nearest(
+        &self,
+        lat: f64,
+        lon: f64,
+        k: usize,
+        org: &str,
+        date_range: [f64; 2],
+        sentiment_range: [f64; 2],
+    ) -> JsValue {
+        let query_point = [lon, lat];
+        let candidate_pool = (k * 8).max(64);
+
+        let mut filtered_events: Vec<(&ActivistEvent, f64)> = self
+            .spatial_index
+            .nearest_neighbor_iter(&query_point)
+            .map(|rect| &rect.data)
+            .filter(|event| matches_filters(event, org, date_range, sentiment_range))
+            .take(candidate_pool)
+            .map(|event| {
+                let distance = haversine_distance_meters(
+                    lat,
+                    lon,
+                    event.geometry[1] as f64,
+                    event.geometry[0] as f64,
+                );
+                (event, distance)
+            })
+            .collect();
+
+        filtered_events.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        filtered_events.truncate(k);
+
+        let filtered_events: Vec<&ActivistEvent> =
+            filtered_events.into_iter().map(|(event, _)| event).collect();
+
+        let geojson = serde_json::to_string(&filtered_events).unwrap();
+        JsValue::from(geojson)
+    }
+
+    /// Returns the `k` events whose embeddings are most similar to
+    /// `query_vector`, searched via the HNSW index built during `load_data`.
+    /// Events with no embedding never became HNSW nodes, so node ids are
+    /// translated back to `events` indices through `embedding_node_to_event`
+    /// rather than assumed to line up positionally. Empty graphs and empty
+    /// query vectors return no results.
+    #[wasm_bindgen]
+    pub fn similar(&self, query_vector: Vec<f32>, k: usize) -> JsValue {
+        let ef = k.max(50);
+        let results: Vec<&ActivistEvent> = self
+            .embedding_index
+            .search(&query_vector, k, ef)
+            .into_iter()
+            .map(|(node, _)| &self.events[self.embedding_node_to_event[node as usize] as usize])
+            .collect();
+
+        let geojson = serde_json::to_string(&results).unwrap();
+        JsValue::from(geojson)
+    }
+
+    /// Like `apply_filters`, but additionally ranks the filtered events by
+    /// one or more MeiliSearch-style sort terms, e.g.
+    /// `["date:desc", "sentiment:asc", "_geoPoint(48.8566,2.3522):asc"]`.
+    /// Earlier terms take priority; ties fall through to the next term via a
+    /// stable sort. Unrecognized terms are silently dropped.
+    #[wasm_bindgen]
+    pub fn apply_filters_sorted(
+        &self,
+        org: &str,
+        date_range: [f64; 2],
+        sentiment_range: [f64; 2],
+        sort: Vec<String>,
+    ) -> JsValue {
+        let sort_keys: Vec<(SortKey, SortDirection)> =
+            sort.iter().filter_map(|term| parse_sort_term(term)).collect();
+
+        let mut filtered_events: Vec<&ActivistEvent> = self
+            .events
+            .iter()
+            .filter(|event| matches_filters(event, org, date_range, sentiment_range))
+            .collect();
+
+        filtered_events.sort_by(|a, b| {
+            for (key, direction) in &sort_keys {
+                let ordering = compare_by(a, b, key, direction);
+                if ordering != std::cmp::Ordering::Equal {
+                    return ordering;
+                }
+            }
+            std::cmp::Ordering::Equal
+        });
+
+        let geojson = serde_json::to_string(&filtered_events).unwrap();
+        JsValue::from(geojson)
+    }
+
+    /// Parses a GeoJSON Text Sequence (RFC 8142) — newline-terminated
+    /// Features, each prefixed by the ASCII Record Separator (`0x1e`) — and
+    /// inserts the parsed events into both `events` and `spatial_index`.
+    /// Lets callers stream large datasets incrementally instead of
+    /// materializing one monolithic FeatureCollection document.
+    #[wasm_bindgen]
+    pub fn load_geojsonseq(&mut self, data: &[u8]) -> Result<(), JsValue> {
+        const RECORD_SEPARATOR: u8 = 0x1e;
+
+        for record in data.split(|&b| b == RECORD_SEPARATOR) {
+            let text = std::str::from_utf8(record)
+                .map_err(|e| JsValue::from(e.to_string()))?
+                .trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            let feature: Feature = text
+                .parse()
+                .map_err(|e: geojson::Error| JsValue::from(e.to_string()))?;
+
+            let Some(point) = feature.geometry.as_ref().and_then(geometry_to_point) else {
+                continue;
+            };
+
+            let properties = feature.properties.unwrap_or_default();
+            let event = ActivistEvent {
+                id: feature.id.map(|id| id.to_string()).unwrap_or_default(),
+                org: properties
+                    .get("org")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                // `export_geojsonseq` writes the parsed ordinal back as a
+                // number; fuzzy date strings go through `parse_fuzzy_date`.
+                date: properties
+                    .get("date")
+                    .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(parse_fuzzy_date)))
+                    .unwrap_or(0.0),
+                sentiment: properties
+                    .get("sentiment")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0) as f32,
+                momentum: properties
+                    .get("momentum")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0) as f32,
+                geometry: point.clone(),
+                embedding: properties
+                    .get("_vectors")
+                    .or_else(|| properties.get("embedding"))
+                    .and_then(|v| v.as_array())
+                    .map(|arr| arr.iter().filter_map(|x| x.as_f64()).map(|f| f as f32).collect())
+                    .unwrap_or_default(),
+            };
+
+            self.spatial_index.insert(RectangleWithData {
+                mbr: [point[0], point[1], point[0], point[1]],
+                data: event.clone(),
+            });
+
+            let event_index = self.events.len();
+            if self
+                .embedding_index
+                .insert(event.embedding.clone(), &mut || rand::thread_rng().gen::<f64>())
+                .is_some()
+            {
+                self.embedding_node_to_event.push(event_index as u32);
+            }
+            self.events.push(event);
+        }
+
+        Ok(())
+    }
+
+    /// Serializes all events as a GeoJSON Text Sequence (RFC 8142): one
+    /// Feature per event, each prefixed by the Record Separator and
+    /// terminated by `\n`.
+    #[wasm_bindgen]
+    pub fn export_geojsonseq(&self) -> Vec<u8> {
+        const RECORD_SEPARATOR: u8 = 0x1e;
+        let mut out = Vec::new();
+
+        for event in &self.events {
+            let feature = Feature {
+                bbox: None,
+                geometry: Some(Geometry::new(GeoJsonValue::Point(vec![
+                    event.geometry[0] as f64,
+                    event.geometry[1] as f64,
+                ]))),
+                id: Some(geojson::feature::Id::String(event.id.clone())),
+                properties: Some(
+                    serde_json::json!({
+                        "org": event.org,
+                        "date": event.date,
+                        "sentiment": event.sentiment,
+                        "momentum": event.momentum,
+                        "embedding": event.embedding,
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                ),
+                foreign_members: None,
+            };
+
+            out.push(RECORD_SEPARATOR);
+            out.extend_from_slice(feature.to_string().as_bytes());
+            out.push(b'\n');
+        }
+
+        out
+    }
+
+    /// Computes per-field facet distributions over the events matching the
+    /// given org/date/sentiment filters, the wasm analogue of MeiliSearch's
+    /// `facetDistribution`. `fields` is a JS array of field names to compute
+    /// (`"org"`, `"sentiment"`, `"momentum"`, `"date"`); unrecognized names
+    /// are ignored. Numeric fields report min/max plus a `buckets`-wide
+    /// histogram, computed over the filtered slice so a dashboard can render
+    /// breakdowns without a second round trip.
+    #[wasm_bindgen]
+    pub fn facet_distribution(
+        &self,
+        org: &str,
+        date_range: [f64; 2],
+        sentiment_range: [f64; 2],
+        fields: JsValue,
+        buckets: u32,
+    ) -> JsValue {
+        let fields: Vec<String> = serde_wasm_bindgen::from_value(fields).unwrap_or_default();
+
+        let filtered_events: Vec<&ActivistEvent> = self
+            .events
+            .iter()
+            .filter(|event| matches_filters(event, org, date_range, sentiment_range))
+            .collect();
+
+        let mut result: HashMap<String, FacetValue> = HashMap::new();
+
+        for field in &fields {
+            let facet = match field.as_str() {
+                "org" => {
+                    let mut counts: HashMap<String, u32> = HashMap::new();
+                    for event in &filtered_events {
+                        *counts.entry(event.org.clone()).or_insert(0) += 1;
+                    }
+                    FacetValue::Categorical(counts)
+                }
+                "sentiment" => {
+                    numeric_facet(filtered_events.iter().map(|e| e.sentiment as f64), buckets)
+                }
+                "momentum" => {
+                    numeric_facet(filtered_events.iter().map(|e| e.momentum as f64), buckets)
+                }
+                "date" => numeric_facet(filtered_events.iter().map(|e| e.date), buckets),
+                _ => continue,
+            };
+            result.insert(field.clone(), facet);
+        }
+
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
     #[wasm_bindgen]
     pub fn export_csv(&self) -> Vec<u8> {
         let mut wtr = WriterBuilder::new().from_writer(vec![]);
@@ -139,3 +1138,380 @@ impl GeoProcessor {
         self.events.len() as u32 * std::mem::size_of::<ActivistEvent>() as u32
     }
 }
+
+#[cfg(test)]
+mod fuzzy_date_tests {
+    use super::parse_fuzzy_date;
+
+    #[test]
+    fn bare_year() {
+        assert_eq!(parse_fuzzy_date("1885"), Some(1885.0));
+    }
+
+    #[test]
+    fn decade_suffix() {
+        assert_eq!(parse_fuzzy_date("1990s"), Some(1990.0));
+    }
+
+    #[test]
+    fn approximate_prefix() {
+        assert_eq!(parse_fuzzy_date("~1885"), Some(1885.0));
+        assert_eq!(parse_fuzzy_date("before 1800"), Some(1800.0));
+    }
+
+    #[test]
+    fn century_notation() {
+        assert_eq!(parse_fuzzy_date("C19"), Some(1851.0));
+        assert_eq!(parse_fuzzy_date("early C19"), Some(1821.0));
+        assert_eq!(parse_fuzzy_date("late C19"), Some(1881.0));
+    }
+
+    #[test]
+    fn year_month_and_year_month_day() {
+        assert_eq!(parse_fuzzy_date("2011-03"), Some(2011.0 + 2.0 / 12.0));
+        assert_eq!(
+            parse_fuzzy_date("2011-03-01"),
+            Some(2011.0 + 2.0 / 12.0)
+        );
+    }
+
+    #[test]
+    fn month_slash_year() {
+        assert_eq!(parse_fuzzy_date("05/2014"), Some(2014.0 + 4.0 / 12.0));
+    }
+
+    #[test]
+    fn year_range_takes_start() {
+        assert_eq!(parse_fuzzy_date("1990-1999"), Some(1990.0));
+    }
+
+    #[test]
+    fn unparsable_returns_none() {
+        assert_eq!(parse_fuzzy_date("not a date"), None);
+    }
+}
+
+#[cfg(test)]
+mod hnsw_tests {
+    use super::{ActivistEvent, GeoProcessor, HnswIndex};
+
+    fn rng() -> impl FnMut() -> f64 {
+        let mut seed = 1u64;
+        move || {
+            // xorshift — deterministic so tests aren't flaky.
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % 1_000_000) as f64 / 1_000_000.0
+        }
+    }
+
+    #[test]
+    fn empty_graph_returns_nothing() {
+        let index = HnswIndex::new(16, 200);
+        assert!(index.search(&[1.0, 0.0], 5, 50).is_empty());
+    }
+
+    #[test]
+    fn first_node_becomes_entry_point() {
+        let mut index = HnswIndex::new(16, 200);
+        let mut rng = rng();
+        let node = index.insert(vec![1.0, 0.0, 0.0, 0.0], &mut rng);
+        assert_eq!(node, Some(0));
+        assert_eq!(index.entry_point, Some(0));
+    }
+
+    #[test]
+    fn zero_length_vector_is_rejected() {
+        let mut index = HnswIndex::new(16, 200);
+        let mut rng = rng();
+        assert_eq!(index.insert(Vec::new(), &mut rng), None);
+        assert!(index.entry_point.is_none());
+    }
+
+    #[test]
+    fn duplicate_vectors_both_searchable() {
+        let mut index = HnswIndex::new(16, 200);
+        let mut rng = rng();
+        index.insert(vec![1.0, 0.0, 0.0, 0.0], &mut rng);
+        index.insert(vec![1.0, 0.0, 0.0, 0.0], &mut rng);
+        let results = index.search(&[1.0, 0.0, 0.0, 0.0], 2, 50);
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn node_id_maps_back_through_embedding_node_to_event() {
+        // An earlier event with no embedding never becomes an HNSW node, so
+        // node ids and `events` indices diverge from here on — regression
+        // test for that misalignment.
+        let mut processor = GeoProcessor::new();
+        processor.events.push(ActivistEvent {
+            id: "no-embedding".to_string(),
+            org: "Org".to_string(),
+            date: 2000.0,
+            sentiment: 0.0,
+            momentum: 0.0,
+            geometry: vec![0.0, 0.0],
+            embedding: Vec::new(),
+        });
+
+        let mut rng = rng();
+        let with_embedding = ActivistEvent {
+            id: "with-embedding".to_string(),
+            org: "Org".to_string(),
+            date: 2001.0,
+            sentiment: 0.0,
+            momentum: 0.0,
+            geometry: vec![1.0, 1.0],
+            embedding: vec![1.0, 0.0, 0.0, 0.0],
+        };
+
+        let event_index = processor.events.len();
+        if processor
+            .embedding_index
+            .insert(with_embedding.embedding.clone(), &mut rng)
+            .is_some()
+        {
+            processor.embedding_node_to_event.push(event_index as u32);
+        }
+        processor.events.push(with_embedding);
+
+        let (node, _) = processor.embedding_index.search(&[1.0, 0.0, 0.0, 0.0], 1, 50)[0];
+        let mapped_index = processor.embedding_node_to_event[node as usize] as usize;
+
+        assert_eq!(processor.events[mapped_index].id, "with-embedding");
+    }
+}
+
+#[cfg(test)]
+mod sort_tests {
+    use super::{compare_by, parse_sort_term, ActivistEvent, SortDirection, SortKey};
+
+    fn event(id: &str, date: f64, sentiment: f32, lat: f64, lon: f64) -> ActivistEvent {
+        ActivistEvent {
+            id: id.to_string(),
+            org: "Org".to_string(),
+            date,
+            sentiment,
+            momentum: 0.0,
+            geometry: vec![lon as f32, lat as f32],
+            embedding: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_simple_field_terms() {
+        assert!(matches!(
+            parse_sort_term("date:desc"),
+            Some((SortKey::Date, SortDirection::Desc))
+        ));
+        assert!(matches!(
+            parse_sort_term("sentiment:asc"),
+            Some((SortKey::Sentiment, SortDirection::Asc))
+        ));
+        assert!(matches!(
+            parse_sort_term("momentum:asc"),
+            Some((SortKey::Momentum, SortDirection::Asc))
+        ));
+    }
+
+    #[test]
+    fn parses_geo_point_term() {
+        match parse_sort_term("_geoPoint(48.8566,2.3522):asc") {
+            Some((SortKey::GeoDistance { lat, lon }, SortDirection::Asc)) => {
+                assert_eq!(lat, 48.8566);
+                assert_eq!(lon, 2.3522);
+            }
+            other => panic!("expected GeoDistance/Asc, got {:?}", other.is_some()),
+        }
+    }
+
+    #[test]
+    fn malformed_terms_return_none() {
+        assert!(parse_sort_term("date").is_none());
+        assert!(parse_sort_term("date:sideways").is_none());
+        assert!(parse_sort_term("unknown_field:asc").is_none());
+        assert!(parse_sort_term("_geoPoint(not-a-number,2.0):asc").is_none());
+        assert!(parse_sort_term("_geoPoint(48.8566):asc").is_none());
+    }
+
+    #[test]
+    fn geo_point_ties_fall_through_to_secondary_key() {
+        // Same distance from the query point (symmetric around lon 0), so the
+        // primary _geoPoint term alone can't order them — the secondary
+        // date:asc term must break the tie.
+        let a = event("a", 2001.0, 0.0, 0.0, -1.0);
+        let b = event("b", 2000.0, 0.0, 0.0, 1.0);
+
+        let primary = (SortKey::GeoDistance { lat: 0.0, lon: 0.0 }, SortDirection::Asc);
+        assert_eq!(
+            compare_by(&a, &b, &primary.0, &primary.1),
+            std::cmp::Ordering::Equal
+        );
+
+        let mut events = vec![&a, &b];
+        let sort_keys = vec![
+            primary,
+            (SortKey::Date, SortDirection::Asc),
+        ];
+        events.sort_by(|x, y| {
+            for (key, direction) in &sort_keys {
+                let ordering = compare_by(x, y, key, direction);
+                if ordering != std::cmp::Ordering::Equal {
+                    return ordering;
+                }
+            }
+            std::cmp::Ordering::Equal
+        });
+
+        assert_eq!(events[0].id, "b");
+        assert_eq!(events[1].id, "a");
+    }
+}
+
+#[cfg(test)]
+mod geojsonseq_tests {
+    use super::GeoProcessor;
+
+    #[test]
+    fn export_then_load_round_trips_events() {
+        let mut processor = GeoProcessor::new();
+        processor
+            .load_geojsonseq(
+                b"\x1e{\"type\":\"Feature\",\"id\":\"a\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[2.3522,48.8566]},\"properties\":{\"org\":\"Org\",\"date\":\"1990\",\"sentiment\":0.5,\"momentum\":0.1}}\n\
+                  \x1e{\"type\":\"Feature\",\"id\":\"b\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[-0.1276,51.5072]},\"properties\":{\"org\":\"Org\",\"date\":\"2000\",\"sentiment\":-0.2,\"momentum\":0.0}}\n",
+            )
+            .unwrap();
+        assert_eq!(processor.events.len(), 2);
+
+        let exported = processor.export_geojsonseq();
+
+        let mut reloaded = GeoProcessor::new();
+        reloaded.load_geojsonseq(&exported).unwrap();
+
+        assert_eq!(reloaded.events.len(), 2);
+        assert_eq!(reloaded.events[0].id, "a");
+        assert_eq!(reloaded.events[0].date, 1990.0);
+        assert_eq!(reloaded.events[1].id, "b");
+        assert_eq!(reloaded.events[1].geometry, vec![-0.1276, 51.5072]);
+    }
+
+    #[test]
+    fn export_frames_each_record_with_rs_and_newline() {
+        let mut processor = GeoProcessor::new();
+        processor
+            .load_geojsonseq(
+                b"\x1e{\"type\":\"Feature\",\"id\":\"a\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[0.0,0.0]},\"properties\":{\"org\":\"Org\",\"date\":\"1990\"}}\n",
+            )
+            .unwrap();
+
+        let exported = processor.export_geojsonseq();
+        assert_eq!(exported[0], 0x1e);
+        assert_eq!(*exported.last().unwrap(), b'\n');
+        assert_eq!(exported.iter().filter(|&&b| b == 0x1e).count(), 1);
+    }
+
+    #[test]
+    fn empty_and_blank_records_are_skipped() {
+        let mut processor = GeoProcessor::new();
+        processor
+            .load_geojsonseq(
+                b"\x1e\x1e   \x1e{\"type\":\"Feature\",\"id\":\"a\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[0.0,0.0]},\"properties\":{\"org\":\"Org\",\"date\":\"1990\"}}\n",
+            )
+            .unwrap();
+
+        assert_eq!(processor.events.len(), 1);
+        assert_eq!(processor.events[0].id, "a");
+    }
+
+    #[test]
+    fn non_point_geometry_is_skipped() {
+        let mut processor = GeoProcessor::new();
+        processor
+            .load_geojsonseq(
+                b"\x1e{\"type\":\"Feature\",\"id\":\"a\",\"geometry\":{\"type\":\"LineString\",\"coordinates\":[[0.0,0.0],[1.0,1.0]]},\"properties\":{\"org\":\"Org\"}}\n",
+            )
+            .unwrap();
+
+        assert!(processor.events.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod facet_tests {
+    use super::{numeric_facet, FacetValue};
+
+    fn histogram(values: Vec<f64>, buckets: u32) -> (f64, f64, Vec<u32>) {
+        match numeric_facet(values.into_iter(), buckets) {
+            FacetValue::Numeric { min, max, histogram } => (min, max, histogram),
+            FacetValue::Categorical(_) => panic!("expected a numeric facet"),
+        }
+    }
+
+    #[test]
+    fn no_events_returns_zeroed_histogram() {
+        let (min, max, histogram) = histogram(Vec::new(), 4);
+        assert_eq!((min, max), (0.0, 0.0));
+        assert_eq!(histogram, vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn single_event_lands_in_first_bucket() {
+        let (min, max, histogram) = histogram(vec![42.0], 4);
+        assert_eq!((min, max), (42.0, 42.0));
+        assert_eq!(histogram, vec![1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn all_equal_values_avoid_division_by_zero() {
+        // min == max, so span must fall back to a non-zero epsilon rather
+        // than dividing by zero and producing NaN bucket indices.
+        let (min, max, histogram) = histogram(vec![5.0, 5.0, 5.0], 3);
+        assert_eq!((min, max), (5.0, 5.0));
+        assert_eq!(histogram.iter().sum::<u32>(), 3);
+    }
+
+    #[test]
+    fn zero_buckets_is_treated_as_one() {
+        let (_, _, histogram) = histogram(vec![1.0, 2.0, 3.0], 0);
+        assert_eq!(histogram.len(), 1);
+        assert_eq!(histogram[0], 3);
+    }
+
+    #[test]
+    fn max_value_lands_in_last_bucket_not_past_it() {
+        let (_, _, histogram) = histogram(vec![0.0, 10.0], 2);
+        assert_eq!(histogram, vec![1, 1]);
+    }
+}
+
+#[cfg(test)]
+mod load_data_tests {
+    use super::parallel_chunk_size;
+
+    #[test]
+    fn divides_evenly_across_parallelism() {
+        assert_eq!(parallel_chunk_size(100, 4), 25);
+    }
+
+    #[test]
+    fn rounds_down_when_not_evenly_divisible() {
+        assert_eq!(parallel_chunk_size(10, 3), 3);
+    }
+
+    #[test]
+    fn empty_feature_set_never_yields_a_zero_sized_chunk() {
+        // par_chunks panics on a chunk size of 0.
+        assert_eq!(parallel_chunk_size(0, 4), 1);
+    }
+
+    #[test]
+    fn fewer_features_than_threads_yields_chunk_size_of_one() {
+        assert_eq!(parallel_chunk_size(2, 8), 1);
+    }
+
+    #[test]
+    fn zero_measured_parallelism_is_treated_as_one() {
+        assert_eq!(parallel_chunk_size(10, 0), 10);
+    }
+}